@@ -0,0 +1,330 @@
+use hyper::{Method, Request, StatusCode};
+use hyper::header::Headers;
+
+use state::State;
+use router::route::matcher::{AllowHeader, RouteMatcher, RouteNonMatch};
+use http::request::query_string;
+
+/// Combines two `RouteMatcher` values into a single matcher which only succeeds if both inner
+/// matchers succeed, short-circuiting on (and reporting) whichever matcher rejects the request
+/// first.
+///
+/// `RouteNonMatch::union` is deliberately not used to combine a rejection from `t` with one from
+/// `u`: it exists to OR together rejections from *sibling* routes at the same tree node (see
+/// `router::route::matcher::RouteNonMatch`), and using it here to AND two matchers onto the same
+/// route would misreport, for instance, a method mismatch combined with a missing header as if
+/// either condition alone were enough to satisfy the route.
+///
+/// When `t` matches but `u` rejects with no `Allow` information of its own (the case for every
+/// non-method matcher: `HeaderRequiredRouteMatcher`, `AcceptHeaderRouteMatcher`,
+/// `QueryStringExistsRouteMatcher`, ...), `u`'s rejection is reported with `t`'s `allow_methods`
+/// grafted onto it instead. Without this, a route whose method matched but whose header/Accept/
+/// query matcher didn't would report no method information at all, and a sibling route's plain
+/// method mismatch could then union into a `405 Allow` header that omits a method this route does
+/// in fact accept (just not for this particular request). If `u` does carry its own `Allow`
+/// information (e.g. it's itself a nested `AndRouteMatcher` around a method check), that real
+/// information is kept as-is rather than overwritten.
+///
+/// Created via `SingleRouteBuilder::add_matcher`, rather than being constructed directly.
+pub struct AndRouteMatcher<T, U>
+where
+    T: RouteMatcher,
+    U: RouteMatcher,
+{
+    t: T,
+    u: U,
+}
+
+impl<T, U> AndRouteMatcher<T, U>
+where
+    T: RouteMatcher,
+    U: RouteMatcher,
+{
+    pub(crate) fn new(t: T, u: U) -> Self {
+        AndRouteMatcher { t, u }
+    }
+}
+
+impl<T, U> RouteMatcher for AndRouteMatcher<T, U>
+where
+    T: RouteMatcher,
+    U: RouteMatcher,
+{
+    fn is_match(&self, state: &State, req: &Request) -> Result<(), RouteNonMatch> {
+        self.t.is_match(state, req)?;
+        self.u.is_match(state, req).map_err(|non_match| {
+            // Only graft `t`'s accepted methods on if `u`'s own rejection didn't already carry
+            // method information of its own (e.g. `u` being a nested `AndRouteMatcher` around
+            // another method check) — that real information must win over a recovered guess.
+            if *non_match.allow() != AllowHeader::None {
+                return non_match;
+            }
+
+            match self.t.allow_methods() {
+                Some(methods) => {
+                    RouteNonMatch::with_status_and_allow(
+                        non_match.status(),
+                        AllowHeader::Some(methods),
+                    )
+                }
+                None => non_match,
+            }
+        })
+    }
+
+    fn allow_methods(&self) -> Option<Vec<Method>> {
+        match (self.t.allow_methods(), self.u.allow_methods()) {
+            (Some(mut a), Some(b)) => {
+                for method in b {
+                    if !a.contains(&method) {
+                        a.push(method);
+                    }
+                }
+                Some(a)
+            }
+            (Some(a), None) => Some(a),
+            (None, Some(b)) => Some(b),
+            (None, None) => None,
+        }
+    }
+}
+
+/// A `RouteMatcher` that requires a header of the given name to be present on the request, and
+/// optionally that it carries a specific value.
+///
+/// Use `HeaderRequiredRouteMatcher::new` to match on presence alone, or
+/// `HeaderRequiredRouteMatcher::with_value` to additionally require a value.
+pub struct HeaderRequiredRouteMatcher {
+    name: String,
+    value: Option<String>,
+}
+
+impl HeaderRequiredRouteMatcher {
+    /// Matches any request which carries a header named `name`, regardless of its value.
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        HeaderRequiredRouteMatcher {
+            name: name.into(),
+            value: None,
+        }
+    }
+
+    /// Matches any request which carries a header named `name` with exactly the given `value`.
+    pub fn with_value<S: Into<String>>(name: S, value: S) -> Self {
+        HeaderRequiredRouteMatcher {
+            name: name.into(),
+            value: Some(value.into()),
+        }
+    }
+}
+
+impl RouteMatcher for HeaderRequiredRouteMatcher {
+    fn is_match(&self, _state: &State, req: &Request) -> Result<(), RouteNonMatch> {
+        let headers: &Headers = req.headers();
+
+        let matched = match headers.get_raw(&self.name) {
+            Some(raw) => match self.value {
+                None => true,
+                Some(ref expected) => raw.iter().any(|v| v.as_slice() == expected.as_bytes()),
+            },
+            None => false,
+        };
+
+        if matched {
+            Ok(())
+        } else {
+            Err(RouteNonMatch::new(StatusCode::BadRequest))
+        }
+    }
+}
+
+/// A `RouteMatcher` that performs `Accept` header negotiation, succeeding only if the request
+/// accepts one of the media types provided at construction (or sends no `Accept` header at all,
+/// which is treated as accepting anything).
+pub struct AcceptHeaderRouteMatcher {
+    supported_media_types: Vec<String>,
+}
+
+impl AcceptHeaderRouteMatcher {
+    pub fn new(supported_media_types: Vec<String>) -> Self {
+        AcceptHeaderRouteMatcher { supported_media_types }
+    }
+}
+
+/// Splits a media type such as `"application/json"` or `"*/*"` into its `(type, subtype)` parts,
+/// ignoring any `;`-delimited parameters (e.g. the `q` weight on an `Accept` entry).
+fn media_type_parts(media_type: &str) -> (&str, &str) {
+    let media_type = media_type.split(';').next().unwrap_or(media_type).trim();
+    match media_type.find('/') {
+        Some(i) => (&media_type[..i], &media_type[i + 1..]),
+        None => (media_type, ""),
+    }
+}
+
+/// Whether an `Accept` entry matches a supported media type, honouring the `*/*` and `type/*`
+/// wildcard forms (but not substring matches like `"application/json"` against
+/// `"application/jsonp"`).
+fn media_type_matches(accept: &str, supported: &str) -> bool {
+    let (accept_type, accept_subtype) = media_type_parts(accept);
+    let (supported_type, supported_subtype) = media_type_parts(supported);
+
+    (accept_type == "*" || accept_type == supported_type) &&
+        (accept_subtype == "*" || accept_subtype == supported_subtype)
+}
+
+impl RouteMatcher for AcceptHeaderRouteMatcher {
+    fn is_match(&self, _state: &State, req: &Request) -> Result<(), RouteNonMatch> {
+        match req.headers().get_raw("Accept") {
+            None => Ok(()),
+            Some(raw) => {
+                let accepted: Vec<String> = raw.iter()
+                    .filter_map(|v| String::from_utf8(v.to_vec()).ok())
+                    .flat_map(|v| v.split(',').map(str::trim).map(str::to_owned).collect::<Vec<_>>())
+                    .collect();
+
+                let matched = accepted.iter().any(|accept| {
+                    self.supported_media_types
+                        .iter()
+                        .any(|m| media_type_matches(accept, m))
+                });
+
+                if matched {
+                    Ok(())
+                } else {
+                    Err(RouteNonMatch::new(StatusCode::NotAcceptable))
+                }
+            }
+        }
+    }
+}
+
+/// A `RouteMatcher` that requires a query string parameter named `name` to be present, regardless
+/// of its value.
+pub struct QueryStringExistsRouteMatcher {
+    name: String,
+}
+
+impl QueryStringExistsRouteMatcher {
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        QueryStringExistsRouteMatcher { name: name.into() }
+    }
+}
+
+impl RouteMatcher for QueryStringExistsRouteMatcher {
+    fn is_match(&self, _state: &State, req: &Request) -> Result<(), RouteNonMatch> {
+        let mapping = query_string::split(req.query());
+
+        if mapping.get(&self.name).is_some() {
+            Ok(())
+        } else {
+            Err(RouteNonMatch::new(StatusCode::BadRequest))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use hyper::StatusCode;
+    use router::route::matcher::AllowHeader;
+
+    struct AlwaysMatcher;
+
+    impl RouteMatcher for AlwaysMatcher {
+        fn is_match(&self, _state: &State, _req: &Request) -> Result<(), RouteNonMatch> {
+            Ok(())
+        }
+    }
+
+    struct NeverMatcher(StatusCode);
+
+    impl RouteMatcher for NeverMatcher {
+        fn is_match(&self, _state: &State, _req: &Request) -> Result<(), RouteNonMatch> {
+            Err(RouteNonMatch::new(self.0))
+        }
+    }
+
+    #[test]
+    fn and_route_matcher_reports_first_rejection_test() {
+        use hyper::Method;
+
+        let state = State::new();
+        let req = Request::new(Method::Get, "/".parse().unwrap());
+
+        // When the first matcher rejects, its rejection is reported as-is, not merged with the
+        // second matcher's outcome via `RouteNonMatch::union` (which would incorrectly claim the
+        // combined Allow set is enough to satisfy this route).
+        let matcher = AndRouteMatcher::new(
+            NeverMatcher(StatusCode::MethodNotAllowed),
+            NeverMatcher(StatusCode::BadRequest),
+        );
+        let non_match = matcher.is_match(&state, &req).unwrap_err();
+        assert_eq!(non_match.status(), StatusCode::MethodNotAllowed);
+        assert_eq!(non_match.allow(), &AllowHeader::None);
+
+        // When only the second matcher rejects, that rejection alone is reported.
+        let matcher = AndRouteMatcher::new(AlwaysMatcher, NeverMatcher(StatusCode::BadRequest));
+        let non_match = matcher.is_match(&state, &req).unwrap_err();
+        assert_eq!(non_match.status(), StatusCode::BadRequest);
+
+        // When both matchers accept, so does the combination.
+        let matcher = AndRouteMatcher::new(AlwaysMatcher, AlwaysMatcher);
+        assert!(matcher.is_match(&state, &req).is_ok());
+    }
+
+    #[test]
+    fn and_route_matcher_recovers_allow_methods_on_second_rejection_test() {
+        use router::route::matcher::MethodOnlyRouteMatcher;
+        use hyper::Method;
+
+        let state = State::new();
+        let req = Request::new(Method::Post, "/".parse().unwrap());
+
+        // The method matcher (`t`) accepts this `POST` request, but the added matcher (`u`)
+        // rejects it for an unrelated reason. The rejection still carries `t`'s accepted methods,
+        // so a sibling route's plain method mismatch can't union into an `Allow` header that
+        // omits `POST` just because this particular `POST` request was missing a header.
+        let matcher = AndRouteMatcher::new(
+            MethodOnlyRouteMatcher::new(vec![Method::Post]),
+            NeverMatcher(StatusCode::BadRequest),
+        );
+        let non_match = matcher.is_match(&state, &req).unwrap_err();
+        assert_eq!(non_match.status(), StatusCode::BadRequest);
+        assert_eq!(non_match.allow(), &AllowHeader::Some(vec![Method::Post]));
+    }
+
+    #[test]
+    fn and_route_matcher_keeps_second_matcher_own_allow_methods_test() {
+        use router::route::matcher::MethodOnlyRouteMatcher;
+        use hyper::Method;
+
+        let state = State::new();
+        let req = Request::new(Method::Get, "/".parse().unwrap());
+
+        // `t` (a `Get`-only matcher) accepts this `GET` request, but `u` is itself a method check
+        // (for `Post`) and correctly rejects. `u`'s own `Allow` set must be kept, not overwritten
+        // with `t`'s — otherwise the resulting `405` would advertise `GET` as acceptable, when
+        // `GET` is precisely the method that was just rejected by the outer route.
+        let matcher = AndRouteMatcher::new(
+            MethodOnlyRouteMatcher::new(vec![Method::Get]),
+            MethodOnlyRouteMatcher::new(vec![Method::Post]),
+        );
+        let non_match = matcher.is_match(&state, &req).unwrap_err();
+        assert_eq!(non_match.status(), StatusCode::MethodNotAllowed);
+        assert_eq!(non_match.allow(), &AllowHeader::Some(vec![Method::Post]));
+    }
+
+    #[test]
+    fn media_type_matches_test() {
+        assert!(media_type_matches("application/json", "application/json"));
+        assert!(media_type_matches("*/*", "application/json"));
+        assert!(media_type_matches("application/*", "application/json"));
+        assert!(media_type_matches(
+            "application/json; q=0.9",
+            "application/json",
+        ));
+
+        assert!(!media_type_matches("application/jsonp", "application/json"));
+        assert!(!media_type_matches("text/plain", "application/json"));
+    }
+}
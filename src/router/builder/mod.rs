@@ -2,6 +2,7 @@
 
 mod single;
 mod replace;
+mod matcher;
 
 use std::marker::PhantomData;
 
@@ -12,13 +13,16 @@ use router::Router;
 use router::tree::TreeBuilder;
 use router::response::finalizer::ResponseFinalizerBuilder;
 use router::route::{Delegation, Extractors, RouteImpl};
-use router::route::matcher::{RouteMatcher, MethodOnlyRouteMatcher};
+use router::route::matcher::{RouteMatcher, MethodOnlyRouteMatcher, AnyRouteMatcher};
 use router::route::dispatch::{PipelineHandleChain, PipelineSet, DispatcherImpl};
 use router::request::path::{PathExtractor, NoopPathExtractor};
 use router::request::query_string::{QueryStringExtractor, NoopQueryStringExtractor};
 use router::tree::node::{SegmentType, NodeBuilder};
 
 pub use self::single::DefineSingleRoute;
+pub use self::matcher::{AndRouteMatcher, HeaderRequiredRouteMatcher, AcceptHeaderRouteMatcher,
+                         QueryStringExistsRouteMatcher};
+pub use router::RouterOptions;
 use self::replace::{ReplacePathExtractor, ReplaceQueryStringExtractor};
 
 /// Builds a `Router` using the provided closure. Routes are defined using the `RouterBuilder`
@@ -54,6 +58,23 @@ use self::replace::{ReplacePathExtractor, ReplaceQueryStringExtractor};
 /// # fn main() { router(); }
 /// ```
 pub fn build_router<C, P, F>(pipeline_chain: C, pipelines: PipelineSet<P>, f: F) -> Router
+where
+    C: PipelineHandleChain<P> + Copy + Send + Sync + 'static,
+    P: Send + Sync + 'static,
+    F: FnOnce(&mut RouterBuilder<C, P>),
+{
+    build_router_with_options(pipeline_chain, pipelines, RouterOptions::default(), f)
+}
+
+/// As `build_router`, but allows the automatic behaviours described by `RouterOptions` (such as
+/// the automatic `405 Method Not Allowed` / `OPTIONS` handling) to be tuned or disabled, for
+/// applications which want to implement that behaviour themselves.
+pub fn build_router_with_options<C, P, F>(
+    pipeline_chain: C,
+    pipelines: PipelineSet<P>,
+    options: RouterOptions,
+    f: F,
+) -> Router
 where
     C: PipelineHandleChain<P> + Copy + Send + Sync + 'static,
     P: Send + Sync + 'static,
@@ -74,7 +95,7 @@ where
         builder.response_finalizer_builder.finalize()
     };
 
-    Router::new(tree_builder.finalize(), response_finalizer)
+    Router::new_with_options(tree_builder.finalize(), response_finalizer, options)
 }
 
 /// Defines functions available on builders that are able to define routes.
@@ -157,12 +178,106 @@ where
         self.request(vec![Method::Post], path)
     }
 
-    // TODO: Glob paths
+    /// Creates a route which matches `PUT` requests to the given path.
+    fn put<'b>(&'b mut self, path: &str) -> DefaultSingleRouteBuilder<'b, C, P> {
+        self.request(vec![Method::Put], path)
+    }
+
+    /// Creates a route which matches `PATCH` requests to the given path.
+    fn patch<'b>(&'b mut self, path: &str) -> DefaultSingleRouteBuilder<'b, C, P> {
+        self.request(vec![Method::Patch], path)
+    }
+
+    /// Creates a route which matches `DELETE` requests to the given path.
+    fn delete<'b>(&'b mut self, path: &str) -> DefaultSingleRouteBuilder<'b, C, P> {
+        self.request(vec![Method::Delete], path)
+    }
+
+    /// Creates a route which matches `OPTIONS` requests to the given path.
+    fn options<'b>(&'b mut self, path: &str) -> DefaultSingleRouteBuilder<'b, C, P> {
+        self.request(vec![Method::Options], path)
+    }
+
+    /// Creates a route which matches `HEAD` requests to the given path.
+    fn head<'b>(&'b mut self, path: &str) -> DefaultSingleRouteBuilder<'b, C, P> {
+        self.request(vec![Method::Head], path)
+    }
+
+    /// Begins defining several routes on the same `path`, one per HTTP method, via the returned
+    /// `MethodRouter`. This collapses the repetitive `get(path).to(a); put(path).to(b); ...`
+    /// pattern used for REST-style resources into a single fluent expression.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate gotham;
+    /// # extern crate hyper;
+    /// # use hyper::{Request, Response};
+    /// # use gotham::state::{State, StateData};
+    /// # use gotham::router::Router;
+    /// # use gotham::router::builder::*;
+    /// # use gotham::router::request::path::PathExtractor;
+    /// # use gotham::router::tree::SegmentMapping;
+    /// # use gotham::middleware::pipeline::new_pipeline;
+    /// # use gotham::middleware::session::NewSessionMiddleware;
+    /// # use gotham::router::route::dispatch::{new_pipeline_set, finalize_pipeline_set};
+    /// # struct ItemParams { id: String }
+    /// # impl StateData for ItemParams {}
+    /// # impl PathExtractor for ItemParams {
+    /// #     fn extract(state: &mut State, segment_mapping: SegmentMapping) -> Result<(), String> {
+    /// #         let id = segment_mapping.get("id").unwrap().first().unwrap().val().to_owned();
+    /// #         state.put(ItemParams { id });
+    /// #         Ok(())
+    /// #     }
+    /// # }
+    /// # fn show(_: State, _: Request) -> (State, Response) { unreachable!() }
+    /// # fn update(_: State, _: Request) -> (State, Response) { unreachable!() }
+    /// # fn destroy(_: State, _: Request) -> (State, Response) { unreachable!() }
+    /// #
+    /// # fn router() -> Router {
+    /// #   let pipelines = new_pipeline_set();
+    /// #   let (pipelines, default) =
+    /// #       pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+    /// #
+    /// #   let pipelines = finalize_pipeline_set(pipelines);
+    /// #
+    /// #   let default_pipeline_chain = (default, ());
+    /// #
+    /// build_router(default_pipeline_chain, pipelines, |route| {
+    ///     // `with_path_extractor` makes the captured `:id` available to `show`/`update`/
+    ///     // `destroy` via `ItemParams`, the same way it would for a single `get`/`put`/...
+    ///     // route built with `SingleRouteBuilder`.
+    ///     route
+    ///         .route_to("/item/:id")
+    ///         .with_path_extractor::<ItemParams>()
+    ///         .get(show)
+    ///         .put(update)
+    ///         .delete(destroy);
+    /// })
+    /// # }
+    /// # fn main() { router(); }
+    /// ```
+    fn route_to<'b>(&'b mut self, path: &str) -> DefaultMethodRouter<'b, C, P> {
+        let (node_builder, pipeline_chain, pipelines) = self.component_refs();
+        let node_builder = descend(node_builder, path);
+
+        MethodRouter {
+            node_builder,
+            pipeline_chain: *pipeline_chain,
+            pipelines: pipelines.clone(),
+            phantom: PhantomData,
+        }
+    }
+
     /// Creates a single route which matches any requests to the given `path` with one of the
-    /// given `methods`. The `path` can consist of static or dynamic segments, for example:
+    /// given `methods`. The `path` can consist of static, dynamic or glob segments, for example:
     ///
     /// * `"/hello/world"` - a static path, matching only a request for exactly `"/hello/world"`
     /// * `"/hello/:name"` - a dynamic path, matching requests for `"/hello/any_value_here"`
+    /// * `"/assets/*path"` - a glob path, matching requests for `"/assets/"` followed by one or
+    ///   more further segments, e.g. `"/assets/js/app.js"`. The glob segment must be the last
+    ///   segment in the path, and the captured remainder is exposed to a `PathExtractor` as a
+    ///   single joined value.
     ///
     /// # Examples
     ///
@@ -216,6 +331,65 @@ where
         }
     }
 
+    /// Begins defining a route that delegates all requests for paths under `path` to another
+    /// `Router`, which is constructed independently (e.g. in another module or crate) and
+    /// attached here as a single unit, rather than being flattened into this tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate gotham;
+    /// # extern crate hyper;
+    /// # use hyper::{Request, Response};
+    /// # use gotham::state::State;
+    /// # use gotham::router::Router;
+    /// # use gotham::router::builder::*;
+    /// # use gotham::middleware::pipeline::new_pipeline;
+    /// # use gotham::middleware::session::NewSessionMiddleware;
+    /// # use gotham::router::route::dispatch::{new_pipeline_set, finalize_pipeline_set};
+    /// # fn my_handler(_: State, _: Request) -> (State, Response) {
+    /// #   unreachable!()
+    /// # }
+    /// #
+    /// # fn api_router() -> Router {
+    /// #   let pipelines = new_pipeline_set();
+    /// #   let (pipelines, default) =
+    /// #       pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+    /// #   let pipelines = finalize_pipeline_set(pipelines);
+    /// #   let default_pipeline_chain = (default, ());
+    /// #   build_router(default_pipeline_chain, pipelines, |route| {
+    /// #       route.get("/list").to(my_handler);
+    /// #   })
+    /// # }
+    /// #
+    /// # fn router() -> Router {
+    /// #   let pipelines = new_pipeline_set();
+    /// #   let (pipelines, default) =
+    /// #       pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+    /// #
+    /// #   let pipelines = finalize_pipeline_set(pipelines);
+    /// #
+    /// #   let default_pipeline_chain = (default, ());
+    /// #
+    /// build_router(default_pipeline_chain, pipelines, |route| {
+    ///     // Requests to `/api/*` are handed off to `api_router()`, with the `/api` prefix
+    ///     // stripped from the path before it reaches the delegated router.
+    ///     route.delegate("/api").to_router(api_router());
+    /// })
+    /// # }
+    /// # fn main() { router(); }
+    /// ```
+    fn delegate<'b>(&'b mut self, path: &str) -> DelegateRouteBuilder<'b, C, P> {
+        let (node_builder, pipeline_chain, pipelines) = self.component_refs();
+        let node_builder = descend(node_builder, path);
+
+        DelegateRouteBuilder {
+            node_builder,
+            pipeline_chain: *pipeline_chain,
+            pipelines: pipelines.clone(),
+        }
+    }
+
     /// Begins defining a new scope, based on a given `path` prefix.
     ///
     /// # Examples
@@ -271,6 +445,66 @@ where
         f(&mut scope_builder)
     }
 
+    /// Begins defining a new scope, based on a given `path` prefix, running a different
+    /// `PipelineHandleChain` for every route defined within it than the one in effect on `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate gotham;
+    /// # extern crate hyper;
+    /// # use hyper::{Request, Response};
+    /// # use gotham::state::State;
+    /// # use gotham::router::Router;
+    /// # use gotham::router::builder::*;
+    /// # use gotham::middleware::pipeline::new_pipeline;
+    /// # use gotham::middleware::session::NewSessionMiddleware;
+    /// # use gotham::router::route::dispatch::{new_pipeline_set, finalize_pipeline_set};
+    /// # mod admin {
+    /// #   use super::*;
+    /// #   pub fn index(_: State, _: Request) -> (State, Response) {
+    /// #       unreachable!()
+    /// #   }
+    /// # }
+    /// #
+    /// # fn router() -> Router {
+    /// #   let pipelines = new_pipeline_set();
+    /// #   let (pipelines, default) =
+    /// #       pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+    /// #   let (pipelines, auth) =
+    /// #       pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+    /// #
+    /// #   let pipelines = finalize_pipeline_set(pipelines);
+    /// #
+    /// #   let default_pipeline_chain = (default, ());
+    /// #   let admin_pipeline_chain = (auth, (default, ()));
+    /// #
+    /// build_router(default_pipeline_chain, pipelines, |route| {
+    ///     route.scope_with_pipeline_chain("/admin", admin_pipeline_chain, |route| {
+    ///         // Requests to `/admin/index` run the `auth` pipeline first.
+    ///         route.get("/index").to(admin::index);
+    ///     });
+    /// })
+    /// # }
+    /// # fn main() { router(); }
+    /// ```
+    fn scope_with_pipeline_chain<NC, F>(&mut self, path: &str, chain: NC, f: F)
+    where
+        NC: PipelineHandleChain<P> + Copy + Send + Sync + 'static,
+        F: FnOnce(&mut ScopeBuilder<NC, P>),
+    {
+        let (node_builder, _pipeline_chain, pipelines) = self.component_refs();
+        let node_builder = descend(node_builder, path);
+
+        let mut scope_builder = ScopeBuilder {
+            node_builder,
+            pipeline_chain: chain,
+            pipelines: pipelines.clone(),
+        };
+
+        f(&mut scope_builder)
+    }
+
     /// Return the components that comprise this builder. For internal use only.
     #[doc(hidden)]
     fn component_refs(&mut self) -> (&mut NodeBuilder, &mut C, &PipelineSet<P>);
@@ -297,6 +531,176 @@ where
     pipelines: PipelineSet<P>,
 }
 
+/// Builder returned by `DrawRoutes::delegate`, used to attach an independently-built `Router` as
+/// the dispatch target for everything beneath the delegated path prefix.
+pub struct DelegateRouteBuilder<'a, C, P>
+where
+    C: PipelineHandleChain<P> + Send + Sync + 'static,
+    P: Send + Sync + 'static,
+{
+    node_builder: &'a mut NodeBuilder,
+    pipeline_chain: C,
+    pipelines: PipelineSet<P>,
+}
+
+impl<'a, C, P> DelegateRouteBuilder<'a, C, P>
+where
+    C: PipelineHandleChain<P> + Send + Sync + 'static,
+    P: Send + Sync + 'static,
+{
+    /// Directs all requests under the delegated path prefix to the given `Router`. The matched
+    /// segments are stripped from the request path before it reaches `router`, so `router` sees
+    /// the same paths it would if it were serving requests directly.
+    pub fn to_router(self, router: Router) {
+        self.to_new_router(router)
+    }
+
+    /// As `to_router`, but accepts any `NewHandler` that produces a `Router`, for parity with
+    /// `to_new_handler` on a regular route.
+    pub fn to_new_router<NR>(self, new_router: NR)
+    where
+        NR: NewHandler + 'static,
+    {
+        let matcher = AnyRouteMatcher::new();
+        let dispatcher = DispatcherImpl::new(new_router, self.pipeline_chain, self.pipelines);
+        let route: RouteImpl<AnyRouteMatcher, NoopPathExtractor, NoopQueryStringExtractor> =
+            RouteImpl::new(
+                matcher,
+                Box::new(dispatcher),
+                Extractors::new(),
+                Delegation::External,
+            );
+        self.node_builder.add_route(Box::new(route));
+    }
+}
+
+/// Builder returned by `DrawRoutes::route_to`, used to attach several per-method handlers to the
+/// same path in a single fluent chain, e.g. `route_to("/item/:id").get(show).put(update)`.
+///
+/// Each method call registers a distinct `RouteImpl` at the shared `NodeBuilder` for the path, so
+/// the existing per-node match loop picks the correct handler for a given request method. The
+/// `Allow`-header logic used for automatic `405`/`OPTIONS` handling is computed by `Router`
+/// itself from the routes registered at a node, not tracked here.
+pub struct MethodRouter<'a, C, P, PE = NoopPathExtractor>
+where
+    C: PipelineHandleChain<P> + Copy + Send + Sync + 'static,
+    P: Send + Sync + 'static,
+    PE: PathExtractor + Send + Sync + 'static,
+{
+    node_builder: &'a mut NodeBuilder,
+    pipeline_chain: C,
+    pipelines: PipelineSet<P>,
+    phantom: PhantomData<PE>,
+}
+
+/// `MethodRouter` as returned by `DrawRoutes::route_to`, before a `with_path_extractor` call has
+/// attached a custom `PathExtractor`.
+pub type DefaultMethodRouter<'a, C, P> = MethodRouter<'a, C, P, NoopPathExtractor>;
+
+impl<'a, C, P, PE> MethodRouter<'a, C, P, PE>
+where
+    C: PipelineHandleChain<P> + Copy + Send + Sync + 'static,
+    P: Send + Sync + 'static,
+    PE: PathExtractor + Send + Sync + 'static,
+{
+    /// Attaches `NPE` as the `PathExtractor` used to populate `State` from any `Dynamic`/`Glob`
+    /// segments captured in the path given to `route_to`, for every handler subsequently attached
+    /// via `get`/`put`/.... Without this, a path such as `"/item/:id"` captures `id` but no
+    /// handler attached here can retrieve it.
+    pub fn with_path_extractor<NPE>(self) -> MethodRouter<'a, C, P, NPE>
+    where
+        NPE: PathExtractor + Send + Sync + 'static,
+    {
+        MethodRouter {
+            node_builder: self.node_builder,
+            pipeline_chain: self.pipeline_chain,
+            pipelines: self.pipelines,
+            phantom: PhantomData,
+        }
+    }
+
+    fn add<H>(&mut self, methods: Vec<Method>, handler: H)
+    where
+        H: Handler + Copy + Send + Sync + 'static,
+    {
+        let matcher = MethodOnlyRouteMatcher::new(methods);
+        let dispatcher = DispatcherImpl::new(move || Ok(handler), self.pipeline_chain, self.pipelines.clone());
+        let route: RouteImpl<MethodOnlyRouteMatcher, PE, NoopQueryStringExtractor> = RouteImpl::new(
+            matcher,
+            Box::new(dispatcher),
+            Extractors::new(),
+            Delegation::Internal,
+        );
+        self.node_builder.add_route(Box::new(route));
+    }
+
+    /// Attaches `handler` for `GET` requests to this path. Unlike `DrawRoutes::get`, this does
+    /// not implicitly pair the handler with `HEAD`, so that a subsequent `.head(...)` call on the
+    /// same chain unambiguously owns the `HEAD` route.
+    pub fn get<H>(mut self, handler: H) -> Self
+    where
+        H: Handler + Copy + Send + Sync + 'static,
+    {
+        self.add(vec![Method::Get], handler);
+        self
+    }
+
+    /// Attaches `handler` for `POST` requests to this path.
+    pub fn post<H>(mut self, handler: H) -> Self
+    where
+        H: Handler + Copy + Send + Sync + 'static,
+    {
+        self.add(vec![Method::Post], handler);
+        self
+    }
+
+    /// Attaches `handler` for `PUT` requests to this path.
+    pub fn put<H>(mut self, handler: H) -> Self
+    where
+        H: Handler + Copy + Send + Sync + 'static,
+    {
+        self.add(vec![Method::Put], handler);
+        self
+    }
+
+    /// Attaches `handler` for `PATCH` requests to this path.
+    pub fn patch<H>(mut self, handler: H) -> Self
+    where
+        H: Handler + Copy + Send + Sync + 'static,
+    {
+        self.add(vec![Method::Patch], handler);
+        self
+    }
+
+    /// Attaches `handler` for `DELETE` requests to this path.
+    pub fn delete<H>(mut self, handler: H) -> Self
+    where
+        H: Handler + Copy + Send + Sync + 'static,
+    {
+        self.add(vec![Method::Delete], handler);
+        self
+    }
+
+    /// Attaches `handler` for `OPTIONS` requests to this path, overriding the automatic
+    /// `OPTIONS` handling that would otherwise be installed for it.
+    pub fn options<H>(mut self, handler: H) -> Self
+    where
+        H: Handler + Copy + Send + Sync + 'static,
+    {
+        self.add(vec![Method::Options], handler);
+        self
+    }
+
+    /// Attaches `handler` for `HEAD` requests to this path.
+    pub fn head<H>(mut self, handler: H) -> Self
+    where
+        H: Handler + Copy + Send + Sync + 'static,
+    {
+        self.add(vec![Method::Head], handler);
+        self
+    }
+}
+
 type DefaultSingleRouteBuilder<'a, C, P> = SingleRouteBuilder<
     'a,
     MethodOnlyRouteMatcher,
@@ -449,17 +853,60 @@ where
             phantom: PhantomData,
         }
     }
-}
-
-fn descend<'n>(node_builder: &'n mut NodeBuilder, path: &str) -> &'n mut NodeBuilder {
-    let path = if path.starts_with("/") {
-        &path[1..]
-    } else {
-        path
-    };
 
-    if path.is_empty() {
-        node_builder
+    /// Replaces the `RouteMatcher` used by this route with `matcher`, discarding the method
+    /// matcher (or any other matcher) that was previously in place.
+    ///
+    /// This allows routes to match on criteria other than the HTTP method, for example a header
+    /// value or query string parameter, via the matchers in `router::builder::matcher`.
+    pub fn with_matcher<NM>(self, matcher: NM) -> SingleRouteBuilder<'a, NM, C, P, PE, QSE>
+    where
+        NM: RouteMatcher + Send + Sync + 'static,
+    {
+        SingleRouteBuilder {
+            node_builder: self.node_builder,
+            matcher,
+            pipeline_chain: self.pipeline_chain,
+            pipelines: self.pipelines,
+            delegation: self.delegation,
+            phantom: PhantomData,
+        }
+    }
+
+    /// ANDs `matcher` onto the `RouteMatcher` already associated with this route, so the request
+    /// must satisfy both the existing matcher (e.g. the HTTP method) and `matcher` in order to be
+    /// dispatched here. The existing matcher is checked first and its rejection, if any, is
+    /// reported as-is; if only `matcher` rejects, its status is reported together with the
+    /// methods the existing matcher accepts (see `AndRouteMatcher`).
+    pub fn add_matcher<NM>(
+        self,
+        matcher: NM,
+    ) -> SingleRouteBuilder<'a, AndRouteMatcher<M, NM>, C, P, PE, QSE>
+    where
+        NM: RouteMatcher + Send + Sync + 'static,
+    {
+        let matcher = AndRouteMatcher::new(self.matcher, matcher);
+
+        SingleRouteBuilder {
+            node_builder: self.node_builder,
+            matcher,
+            pipeline_chain: self.pipeline_chain,
+            pipelines: self.pipelines,
+            delegation: self.delegation,
+            phantom: PhantomData,
+        }
+    }
+}
+
+fn descend<'n>(node_builder: &'n mut NodeBuilder, path: &str) -> &'n mut NodeBuilder {
+    let path = if path.starts_with("/") {
+        &path[1..]
+    } else {
+        path
+    };
+
+    if path.is_empty() {
+        node_builder
     } else {
         build_subtree(node_builder, path.split("/"))
     }
@@ -474,6 +921,8 @@ where
             println!("router::builder::build_subtree descending into {}", segment);
             let (segment, segment_type) = if segment.starts_with(":") {
                 (&segment[1..], SegmentType::Dynamic)
+            } else if segment.starts_with("*") {
+                (&segment[1..], SegmentType::Glob)
             } else {
                 (segment, SegmentType::Static)
             };
@@ -483,8 +932,19 @@ where
                 node.add_child(node_builder);
             }
 
-            let child = node.borrow_mut_child(segment, segment_type).unwrap();
-            build_subtree(child, i)
+            let child = node.borrow_mut_child(segment, segment_type.clone()).unwrap();
+
+            match segment_type {
+                SegmentType::Glob => {
+                    assert!(
+                        i.next().is_none(),
+                        "a glob segment (`*{}`) must be the last segment of a route path",
+                        segment
+                    );
+                    child
+                }
+                _ => build_subtree(child, i),
+            }
         }
         None => {
             println!("router::builder::build_subtree reached node");
@@ -606,6 +1066,187 @@ mod tests {
         }
     }
 
+    struct AssetPath {
+        path: String,
+    }
+
+    impl StateData for AssetPath {}
+
+    impl StaticResponseExtender for AssetPath {
+        fn extend(_: &mut State, _: &mut Response) {}
+    }
+
+    impl PathExtractor for AssetPath {
+        fn extract(state: &mut State, segment_mapping: SegmentMapping) -> Result<(), String> {
+            let path = segment_mapping
+                .get("path")
+                .unwrap()
+                .first()
+                .unwrap()
+                .val()
+                .to_owned();
+            state.put(AssetPath { path });
+            Ok(())
+        }
+    }
+
+    mod assets {
+        use super::*;
+        pub fn serve(mut state: State, _req: Request) -> (State, Response) {
+            let params = state.take::<AssetPath>().unwrap();
+            let response = Response::new()
+                .with_status(StatusCode::Ok)
+                .with_body(params.path);
+            (state, response)
+        }
+    }
+
+    #[test]
+    fn glob_route_test() {
+        let pipelines = new_pipeline_set();
+        let (pipelines, default) =
+            pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+
+        let pipelines = finalize_pipeline_set(pipelines);
+
+        let default_pipeline_chain = (default, ());
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route
+                .get("/assets/*path")
+                .with_path_extractor::<AssetPath>()
+                .to(assets::serve);
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let call = move |req| {
+            let service = new_service.new_service().unwrap();
+            service.call(req).wait().unwrap()
+        };
+
+        let response = call(Request::new(
+            Method::Get,
+            "/assets/js/app.js".parse().unwrap(),
+        ));
+        assert_eq!(response.status(), StatusCode::Ok);
+        let response_bytes = response.body().concat2().wait().unwrap().to_vec();
+        assert_eq!(&String::from_utf8(response_bytes).unwrap(), "js/app.js");
+
+        // A single trailing segment is still captured as the remainder.
+        let response = call(Request::new(
+            Method::Get,
+            "/assets/app.css".parse().unwrap(),
+        ));
+        assert_eq!(response.status(), StatusCode::Ok);
+        let response_bytes = response.body().concat2().wait().unwrap().to_vec();
+        assert_eq!(&String::from_utf8(response_bytes).unwrap(), "app.css");
+    }
+
+    mod index {
+        use super::*;
+        pub fn show(_: State, _: Request) -> (State, Response) {
+            (
+                State::new(),
+                Response::new().with_status(StatusCode::Ok).with_body(
+                    "index",
+                ),
+            )
+        }
+    }
+
+    #[test]
+    fn glob_route_prefers_static_sibling_test() {
+        let pipelines = new_pipeline_set();
+        let (pipelines, default) =
+            pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+
+        let pipelines = finalize_pipeline_set(pipelines);
+
+        let default_pipeline_chain = (default, ());
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route.get("/assets/index.html").to(index::show);
+
+            route
+                .get("/assets/*path")
+                .with_path_extractor::<AssetPath>()
+                .to(assets::serve);
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let call = move |req| {
+            let service = new_service.new_service().unwrap();
+            service.call(req).wait().unwrap()
+        };
+
+        // The more specific static route wins over the glob route registered alongside it.
+        let response = call(Request::new(
+            Method::Get,
+            "/assets/index.html".parse().unwrap(),
+        ));
+        assert_eq!(response.status(), StatusCode::Ok);
+        let response_bytes = response.body().concat2().wait().unwrap().to_vec();
+        assert_eq!(&String::from_utf8(response_bytes).unwrap(), "index");
+
+        // Anything else under the prefix still falls through to the glob route.
+        let response = call(Request::new(
+            Method::Get,
+            "/assets/app.css".parse().unwrap(),
+        ));
+        assert_eq!(response.status(), StatusCode::Ok);
+        let response_bytes = response.body().concat2().wait().unwrap().to_vec();
+        assert_eq!(&String::from_utf8(response_bytes).unwrap(), "app.css");
+    }
+
+    fn delegated_router() -> Router {
+        let pipelines = new_pipeline_set();
+        let (pipelines, default) =
+            pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+
+        let pipelines = finalize_pipeline_set(pipelines);
+
+        let default_pipeline_chain = (default, ());
+
+        build_router(default_pipeline_chain, pipelines, |route| {
+            route.get("/list").to(api::submit);
+        })
+    }
+
+    #[test]
+    fn delegate_test() {
+        let pipelines = new_pipeline_set();
+        let (pipelines, default) =
+            pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+
+        let pipelines = finalize_pipeline_set(pipelines);
+
+        let default_pipeline_chain = (default, ());
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route.delegate("/api").to_router(delegated_router());
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let call = move |req| {
+            let service = new_service.new_service().unwrap();
+            service.call(req).wait().unwrap()
+        };
+
+        let response = call(Request::new(Method::Get, "/api/list".parse().unwrap()));
+        assert_eq!(response.status(), StatusCode::Accepted);
+
+        // A non-standard method is still forwarded to the delegated router (which then applies
+        // its own method negotiation), rather than being rejected as a 404 by the outer router.
+        let response = call(Request::new(
+            Method::Extension("PROPFIND".to_owned()),
+            "/api/list".parse().unwrap(),
+        ));
+        assert_eq!(response.status(), StatusCode::MethodNotAllowed);
+    }
+
     #[test]
     fn build_router_test() {
         let pipelines = new_pipeline_set();
@@ -655,4 +1296,260 @@ mod tests {
         let response_bytes = response.body().concat2().wait().unwrap().to_vec();
         assert_eq!(&String::from_utf8(response_bytes).unwrap(), "16 + 71 = 87");
     }
+
+    #[test]
+    fn auto_method_negotiation_test() {
+        let pipelines = new_pipeline_set();
+        let (pipelines, default) =
+            pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+
+        let pipelines = finalize_pipeline_set(pipelines);
+
+        let default_pipeline_chain = (default, ());
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route.get("/").to(welcome::index);
+            route.post("/").to(welcome::index);
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let call = move |req| {
+            let service = new_service.new_service().unwrap();
+            service.call(req).wait().unwrap()
+        };
+
+        let response = call(Request::new(Method::Put, "/".parse().unwrap()));
+        assert_eq!(response.status(), StatusCode::MethodNotAllowed);
+        // `get` registers both `GET` and `HEAD`, so all three methods are advertised. Each header
+        // line is split on commas, since the typed `Allow` header lists its methods on one line.
+        let allow = response.headers().get_raw("Allow").unwrap();
+        let mut allowed: Vec<String> = allow.iter()
+            .map(|v| String::from_utf8(v.to_vec()).unwrap())
+            .flat_map(|v| v.split(',').map(|m| m.trim().to_owned()).collect::<Vec<_>>())
+            .collect();
+        allowed.sort();
+        assert_eq!(
+            allowed,
+            vec!["GET".to_owned(), "HEAD".to_owned(), "POST".to_owned()]
+        );
+
+        let response = call(Request::new(Method::Options, "/".parse().unwrap()));
+        assert_eq!(response.status(), StatusCode::NoContent);
+
+        let response = call(Request::new(Method::Get, "/not-a-route".parse().unwrap()));
+        assert_eq!(response.status(), StatusCode::NotFound);
+    }
+
+    #[test]
+    fn auto_method_negotiation_opt_out_test() {
+        let pipelines = new_pipeline_set();
+        let (pipelines, default) =
+            pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+
+        let pipelines = finalize_pipeline_set(pipelines);
+
+        let default_pipeline_chain = (default, ());
+
+        let options = RouterOptions { auto_method_negotiation: false };
+
+        let router = build_router_with_options(default_pipeline_chain, pipelines, options, |route| {
+            route.get("/").to(welcome::index);
+            route.post("/").to(welcome::index);
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let call = move |req| {
+            let service = new_service.new_service().unwrap();
+            service.call(req).wait().unwrap()
+        };
+
+        // With automatic method negotiation opted out, a method mismatch falls through to a bare
+        // `404`, with no `Allow` header, instead of an automatic `405`.
+        let response = call(Request::new(Method::Put, "/".parse().unwrap()));
+        assert_eq!(response.status(), StatusCode::NotFound);
+        assert!(response.headers().get_raw("Allow").is_none());
+
+        // Likewise, `OPTIONS` without an explicit route falls through to `404` rather than an
+        // automatic `204`.
+        let response = call(Request::new(Method::Options, "/".parse().unwrap()));
+        assert_eq!(response.status(), StatusCode::NotFound);
+
+        let response = call(Request::new(Method::Get, "/not-a-route".parse().unwrap()));
+        assert_eq!(response.status(), StatusCode::NotFound);
+    }
+
+    struct ItemParams {
+        id: String,
+    }
+
+    impl StateData for ItemParams {}
+
+    impl StaticResponseExtender for ItemParams {
+        fn extend(_: &mut State, _: &mut Response) {}
+    }
+
+    impl PathExtractor for ItemParams {
+        fn extract(state: &mut State, segment_mapping: SegmentMapping) -> Result<(), String> {
+            let id = segment_mapping
+                .get("id")
+                .unwrap()
+                .first()
+                .unwrap()
+                .val()
+                .to_owned();
+            state.put(ItemParams { id });
+            Ok(())
+        }
+    }
+
+    mod item {
+        use super::*;
+        pub fn show(mut state: State, _req: Request) -> (State, Response) {
+            let params = state.take::<ItemParams>().unwrap();
+            let response = Response::new().with_status(StatusCode::Ok).with_body(
+                format!("showing {}", params.id),
+            );
+            (state, response)
+        }
+
+        pub fn destroy(mut state: State, _req: Request) -> (State, Response) {
+            let params = state.take::<ItemParams>().unwrap();
+            let response = Response::new().with_status(StatusCode::Ok).with_body(
+                format!("destroying {}", params.id),
+            );
+            (state, response)
+        }
+    }
+
+    #[test]
+    fn route_to_with_path_extractor_test() {
+        let pipelines = new_pipeline_set();
+        let (pipelines, default) =
+            pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+
+        let pipelines = finalize_pipeline_set(pipelines);
+
+        let default_pipeline_chain = (default, ());
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route
+                .route_to("/item/:id")
+                .with_path_extractor::<ItemParams>()
+                .get(item::show)
+                .delete(item::destroy);
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let call = move |req| {
+            let service = new_service.new_service().unwrap();
+            service.call(req).wait().unwrap()
+        };
+
+        let response = call(Request::new(Method::Get, "/item/42".parse().unwrap()));
+        assert_eq!(response.status(), StatusCode::Ok);
+        let response_bytes = response.body().concat2().wait().unwrap().to_vec();
+        assert_eq!(&String::from_utf8(response_bytes).unwrap(), "showing 42");
+
+        let response = call(Request::new(Method::Delete, "/item/42".parse().unwrap()));
+        assert_eq!(response.status(), StatusCode::Ok);
+        let response_bytes = response.body().concat2().wait().unwrap().to_vec();
+        assert_eq!(&String::from_utf8(response_bytes).unwrap(), "destroying 42");
+
+        let response = call(Request::new(Method::Put, "/item/42".parse().unwrap()));
+        assert_eq!(response.status(), StatusCode::MethodNotAllowed);
+    }
+
+    #[test]
+    fn add_matcher_test() {
+        let pipelines = new_pipeline_set();
+        let (pipelines, default) =
+            pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+
+        let pipelines = finalize_pipeline_set(pipelines);
+
+        let default_pipeline_chain = (default, ());
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route
+                .post("/upload")
+                .add_matcher(HeaderRequiredRouteMatcher::with_value(
+                    "content-type",
+                    "application/json",
+                ))
+                .to(api::submit);
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let call = move |req| {
+            let service = new_service.new_service().unwrap();
+            service.call(req).wait().unwrap()
+        };
+
+        let mut req = Request::new(Method::Post, "/upload".parse().unwrap());
+        req.headers_mut().set_raw("content-type", vec![
+            b"application/json".to_vec(),
+        ]);
+        let response = call(req);
+        assert_eq!(response.status(), StatusCode::Accepted);
+
+        // Right method, but the header required by the added matcher is missing.
+        let response = call(Request::new(Method::Post, "/upload".parse().unwrap()));
+        assert_eq!(response.status(), StatusCode::BadRequest);
+    }
+
+    mod admin {
+        use super::*;
+        pub fn index(_: State, _: Request) -> (State, Response) {
+            (State::new(), Response::new().with_status(StatusCode::Ok))
+        }
+    }
+
+    #[test]
+    fn scope_with_pipeline_chain_test() {
+        let pipelines = new_pipeline_set();
+        let (pipelines, default) =
+            pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+        let (pipelines, auth) =
+            pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+
+        let pipelines = finalize_pipeline_set(pipelines);
+
+        let default_pipeline_chain = (default, ());
+        let admin_pipeline_chain = (auth, (default, ()));
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route.get("/").to(welcome::index);
+
+            route.scope_with_pipeline_chain(
+                "/admin",
+                admin_pipeline_chain,
+                |route| { route.get("/index").to(admin::index); },
+            );
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let call = move |req| {
+            let service = new_service.new_service().unwrap();
+            service.call(req).wait().unwrap()
+        };
+
+        // Routes defined within the scope run under the `admin_pipeline_chain`, but remain
+        // reachable under the scope's path prefix exactly as `scope` would place them.
+        let response = call(Request::new(Method::Get, "/admin/index".parse().unwrap()));
+        assert_eq!(response.status(), StatusCode::Ok);
+
+        // A route defined outside the scope is unaffected, still running the chain passed to
+        // `build_router`.
+        let response = call(Request::new(Method::Get, "/".parse().unwrap()));
+        assert_eq!(response.status(), StatusCode::Ok);
+
+        // The scope's path prefix doesn't leak a route at its own root.
+        let response = call(Request::new(Method::Get, "/admin".parse().unwrap()));
+        assert_eq!(response.status(), StatusCode::NotFound);
+    }
 }
@@ -0,0 +1,172 @@
+//! Defines `Router`, the `Handler` produced by `router::builder::build_router` that walks the
+//! tree it was given to dispatch an incoming request to a matching `Route`.
+
+pub mod builder;
+pub mod request;
+pub mod response;
+pub mod route;
+pub mod tree;
+
+use std::sync::Arc;
+
+use futures::future;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper::header::Allow;
+
+use handler::{Handler, HandlerFuture, NewHandler};
+use router::response::finalizer::ResponseFinalizer;
+use router::route::Route;
+use router::route::matcher::{AllowHeader, RouteNonMatch};
+use router::tree::{Node, SegmentMapping};
+use state::State;
+
+/// Options controlling the router's automatic handling of requests that reach a known path but
+/// don't match any route registered there. See `router::builder::build_router_with_options`.
+#[derive(Clone, Copy)]
+pub struct RouterOptions {
+    /// When `true` (the default), a path with routes registered but none matching the request
+    /// method receives an automatic `405 Method Not Allowed` with an `Allow` header, and an
+    /// `OPTIONS` request to that path without an explicit `OPTIONS` route receives an automatic
+    /// `204` using the same `Allow` header, instead of both falling through to a `404`.
+    pub auto_method_negotiation: bool,
+}
+
+impl Default for RouterOptions {
+    fn default() -> Self {
+        RouterOptions { auto_method_negotiation: true }
+    }
+}
+
+struct RouterData {
+    tree: Node,
+    response_finalizer: ResponseFinalizer,
+    options: RouterOptions,
+}
+
+/// Dispatches requests to the routes assembled by `router::builder::build_router`. Cheaply
+/// `Clone`, so it can be handed to every worker thread that needs to route a request.
+pub struct Router {
+    data: Arc<RouterData>,
+}
+
+impl Router {
+    /// Creates a `Router` from a finalized tree, using the default `RouterOptions`.
+    pub fn new(tree: Node, response_finalizer: ResponseFinalizer) -> Router {
+        Router::new_with_options(tree, response_finalizer, RouterOptions::default())
+    }
+
+    /// As `new`, but with explicit `RouterOptions`.
+    pub fn new_with_options(
+        tree: Node,
+        response_finalizer: ResponseFinalizer,
+        options: RouterOptions,
+    ) -> Router {
+        Router {
+            data: Arc::new(RouterData { tree, response_finalizer, options }),
+        }
+    }
+
+    fn route(&self, state: State, req: Request) -> Box<HandlerFuture> {
+        let path = req.path();
+        let path = if path.starts_with('/') { &path[1..] } else { path };
+
+        let segments: Vec<&str> = if path.is_empty() {
+            Vec::new()
+        } else {
+            path.split('/').collect()
+        };
+
+        match self.data.tree.match_path(&segments) {
+            None => self.respond(state, StatusCode::NotFound, None),
+            Some((node, segment_mapping)) => self.dispatch(node, state, req, segment_mapping),
+        }
+    }
+
+    fn dispatch(
+        &self,
+        node: &Node,
+        state: State,
+        req: Request,
+        segment_mapping: SegmentMapping,
+    ) -> Box<HandlerFuture> {
+        let mut rejection: Option<RouteNonMatch> = None;
+
+        for route in node.routes() {
+            match route.is_match(&state, &req) {
+                Ok(()) => return route.dispatch(state, req, segment_mapping),
+                Err(non_match) => {
+                    rejection = Some(match rejection {
+                        Some(existing) => existing.union(non_match),
+                        None => non_match,
+                    });
+                }
+            }
+        }
+
+        match rejection {
+            None => self.respond(state, StatusCode::NotFound, None),
+            Some(non_match) => self.reject(state, req.method().clone(), non_match),
+        }
+    }
+
+    /// Builds the automatic `404`/`405`/`OPTIONS` response for a node whose routes all rejected
+    /// the request, honouring `RouterOptions::auto_method_negotiation`.
+    fn reject(&self, state: State, method: Method, non_match: RouteNonMatch) -> Box<HandlerFuture> {
+        match non_match.allow().clone() {
+            // A `405`-worthy rejection is the only kind the automatic method negotiation this
+            // router performs applies to. Everything else — including an `AndRouteMatcher`
+            // rejection that carries a recovered `Allow` set alongside a non-`405` status (see
+            // `router::builder::AndRouteMatcher`) — is reported with its own `status` as-is,
+            // regardless of `RouterOptions::auto_method_negotiation`.
+            AllowHeader::Some(methods) if non_match.status() == StatusCode::MethodNotAllowed => {
+                if !self.data.options.auto_method_negotiation {
+                    return self.respond(state, StatusCode::NotFound, None);
+                }
+                self.respond_with_allow(state, method, methods)
+            }
+            _ => self.respond(state, non_match.status(), None),
+        }
+    }
+
+    fn respond_with_allow(&self, state: State, method: Method, methods: Vec<Method>) -> Box<HandlerFuture> {
+        let status = if method == Method::Options {
+            StatusCode::NoContent
+        } else {
+            StatusCode::MethodNotAllowed
+        };
+
+        self.respond(state, status, Some(methods))
+    }
+
+    fn respond(&self, state: State, status: StatusCode, allow: Option<Vec<Method>>) -> Box<HandlerFuture> {
+        let mut response = Response::new().with_status(status);
+
+        if let Some(methods) = allow {
+            response = response.with_header(Allow(methods));
+        }
+
+        self.data.response_finalizer.finalize();
+
+        Box::new(future::ok((state, response)))
+    }
+}
+
+impl NewHandler for Router {
+    type Instance = Router;
+
+    fn new_handler(&self) -> ::std::io::Result<Router> {
+        Ok(self.clone())
+    }
+}
+
+impl Handler for Router {
+    fn handle(self, state: State, req: Request) -> Box<HandlerFuture> {
+        self.route(state, req)
+    }
+}
+
+impl Clone for Router {
+    fn clone(&self) -> Self {
+        Router { data: self.data.clone() }
+    }
+}
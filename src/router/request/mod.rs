@@ -0,0 +1,5 @@
+//! Defines the extractor traits used to pull typed data out of a matched request: `path` for
+//! path segments captured by the router tree, `query_string` for the request's query string.
+
+pub mod path;
+pub mod query_string;
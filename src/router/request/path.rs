@@ -0,0 +1,21 @@
+//! Defines `PathExtractor`, implemented by types that pull data out of the path segments
+//! captured while matching a request against the router tree.
+
+use state::State;
+use router::tree::SegmentMapping;
+
+/// Extracts typed data from the path segments captured for a route (its `Dynamic`/`Glob`
+/// segments) and stores it in `State` for the handler to retrieve.
+pub trait PathExtractor {
+    /// Extracts data from `segment_mapping` and stores it into `state`.
+    fn extract(state: &mut State, segment_mapping: SegmentMapping) -> Result<(), String>;
+}
+
+/// A `PathExtractor` for routes with no `Dynamic`/`Glob` segments, which has nothing to extract.
+pub struct NoopPathExtractor;
+
+impl PathExtractor for NoopPathExtractor {
+    fn extract(_state: &mut State, _segment_mapping: SegmentMapping) -> Result<(), String> {
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+//! Defines `QueryStringExtractor`, implemented by types that pull data out of a request's query
+//! string.
+
+use state::State;
+
+/// Extracts typed data from a request's query string and stores it in `State` for the handler to
+/// retrieve.
+pub trait QueryStringExtractor {
+    /// Extracts data from `query` (the raw query string, if any) and stores it into `state`.
+    fn extract(state: &mut State, query: Option<&str>) -> Result<(), String>;
+}
+
+/// A `QueryStringExtractor` for routes with nothing to extract from the query string.
+pub struct NoopQueryStringExtractor;
+
+impl QueryStringExtractor for NoopQueryStringExtractor {
+    fn extract(_state: &mut State, _query: Option<&str>) -> Result<(), String> {
+        Ok(())
+    }
+}
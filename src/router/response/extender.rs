@@ -0,0 +1,12 @@
+//! Defines `StaticResponseExtender`, implemented by the companion types of a `PathExtractor`/
+//! `QueryStringExtractor` that want a chance to adjust the `Response` when extraction fails.
+
+use hyper::Response;
+
+use state::State;
+
+/// Adjusts a `Response` when the `PathExtractor`/`QueryStringExtractor` paired with this type
+/// fails to extract its data from the request.
+pub trait StaticResponseExtender {
+    fn extend(state: &mut State, response: &mut Response);
+}
@@ -0,0 +1,24 @@
+//! Defines `ResponseFinalizer`, the finalized set of response-adjusting behaviour collected while
+//! building a `Router` via `router::builder::build_router`.
+
+/// The finalized set of response-adjusting behaviour collected while building a `Router`.
+#[derive(Clone)]
+pub struct ResponseFinalizer;
+
+impl ResponseFinalizer {
+    /// Does nothing: it takes no response, and no `StaticResponseExtender` is invoked.
+    pub fn finalize(&self) {}
+}
+
+/// Accumulates finalizer behaviour while a `Router` is being built; see `ResponseFinalizer`.
+pub struct ResponseFinalizerBuilder;
+
+impl ResponseFinalizerBuilder {
+    pub fn new() -> Self {
+        ResponseFinalizerBuilder
+    }
+
+    pub fn finalize(self) -> ResponseFinalizer {
+        ResponseFinalizer
+    }
+}
@@ -0,0 +1,5 @@
+//! Defines types used to adjust a `Response` after routing: `extender` for per-extractor
+//! failure handling, `finalizer` for the finalized set collected while building a `Router`.
+
+pub mod extender;
+pub mod finalizer;
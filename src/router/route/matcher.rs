@@ -0,0 +1,163 @@
+//! Defines `RouteMatcher`, the trait used to decide whether a `Route` should handle the current
+//! request, and `RouteNonMatch`, which records why it was rejected.
+
+use hyper::{Method, Request};
+use hyper::StatusCode;
+
+use state::State;
+
+/// The methods accepted by a route, or a set of sibling routes, for reporting in an `Allow`
+/// header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AllowHeader {
+    /// No method information is carried (the request was rejected on other criteria).
+    None,
+    /// Exactly these methods are accepted.
+    Some(Vec<Method>),
+}
+
+impl AllowHeader {
+    fn union(self, other: AllowHeader) -> AllowHeader {
+        match (self, other) {
+            (AllowHeader::None, other) => other,
+            (this, AllowHeader::None) => this,
+            (AllowHeader::Some(mut a), AllowHeader::Some(b)) => {
+                for method in b {
+                    if !a.contains(&method) {
+                        a.push(method);
+                    }
+                }
+                AllowHeader::Some(a)
+            }
+        }
+    }
+}
+
+/// Describes why a `RouteMatcher` rejected the current request.
+///
+/// `RouteNonMatch` values are combined with `union` across the *sibling* routes registered at a
+/// single tree node (an OR: "did any alternative route here accept this request?"), which is how
+/// the router computes the `Allow` header for an automatic `405`/`OPTIONS` response. A single
+/// route's own compound matcher (see `router::builder::AndRouteMatcher`) does not use `union` for
+/// its internal conjunction, since ANDing matchers together is a different kind of combination
+/// from ORing sibling routes.
+#[derive(Clone, Debug)]
+pub struct RouteNonMatch {
+    status: StatusCode,
+    allow: AllowHeader,
+}
+
+impl RouteNonMatch {
+    /// A rejection with no method information, for matchers that reject a request on criteria
+    /// other than the HTTP method (a header, the query string, ...).
+    pub fn new(status: StatusCode) -> Self {
+        RouteNonMatch { status, allow: AllowHeader::None }
+    }
+
+    /// A rejection caused purely by the request's HTTP method, recording the methods that would
+    /// have matched instead.
+    pub fn with_allow_list(methods: Vec<Method>) -> Self {
+        RouteNonMatch {
+            status: StatusCode::MethodNotAllowed,
+            allow: AllowHeader::Some(methods),
+        }
+    }
+
+    /// Builds a rejection from explicit parts. Used by `router::builder::AndRouteMatcher` to
+    /// recover an earlier matcher's `allow_methods` onto a later matcher's rejection status.
+    pub(crate) fn with_status_and_allow(status: StatusCode, allow: AllowHeader) -> Self {
+        RouteNonMatch { status, allow }
+    }
+
+    /// Combines this rejection with a sibling route's rejection at the same tree node, returning
+    /// the least restrictive outcome: the union of both `Allow` sets, preferring `405` over a more
+    /// generic status if either side's status is `405`. If neither side is `405` and the
+    /// two statuses disagree (e.g. one sibling's matcher rejected with `406`, another's with
+    /// `400`), this arbitrarily keeps `self`'s status — there's no well-defined "more correct"
+    /// choice between two unrelated non-method rejections, so this is a tie-break, not a
+    /// meaningful precedence.
+    pub fn union(self, other: RouteNonMatch) -> RouteNonMatch {
+        let status = match (self.status, other.status) {
+            (s, o) if s == o => s,
+            (StatusCode::MethodNotAllowed, _) | (_, StatusCode::MethodNotAllowed) => {
+                StatusCode::MethodNotAllowed
+            }
+            (s, _) => s,
+        };
+
+        RouteNonMatch { status, allow: self.allow.union(other.allow) }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn allow(&self) -> &AllowHeader {
+        &self.allow
+    }
+}
+
+/// Determines whether a `Route` should be invoked for the current request. Matching on path
+/// segments alone is handled by the router tree; a `RouteMatcher` expresses anything further
+/// (HTTP method, headers, query string, ...).
+pub trait RouteMatcher {
+    /// Returns `Ok(())` if `req` satisfies this matcher, or the `RouteNonMatch` describing why it
+    /// doesn't.
+    fn is_match(&self, state: &State, req: &Request) -> Result<(), RouteNonMatch>;
+
+    /// The methods this matcher would report in an `Allow` header if it were the sole reason a
+    /// route rejected the request, or `None` if this matcher carries no method information (true
+    /// of everything except a method check). Used by `router::builder::AndRouteMatcher` to
+    /// recover an earlier matcher's accepted methods when a later matcher in the same
+    /// conjunction is what actually rejected the request.
+    fn allow_methods(&self) -> Option<Vec<Method>> {
+        None
+    }
+}
+
+/// Matches a request based solely on its HTTP method.
+pub struct MethodOnlyRouteMatcher {
+    methods: Vec<Method>,
+}
+
+impl MethodOnlyRouteMatcher {
+    pub fn new(methods: Vec<Method>) -> Self {
+        MethodOnlyRouteMatcher { methods }
+    }
+
+    /// The methods this matcher accepts, used to build the `Allow` header.
+    pub fn methods(&self) -> &[Method] {
+        &self.methods
+    }
+}
+
+impl RouteMatcher for MethodOnlyRouteMatcher {
+    fn is_match(&self, _state: &State, req: &Request) -> Result<(), RouteNonMatch> {
+        if self.methods.iter().any(|m| m == req.method()) {
+            Ok(())
+        } else {
+            Err(RouteNonMatch::with_allow_list(self.methods.clone()))
+        }
+    }
+
+    fn allow_methods(&self) -> Option<Vec<Method>> {
+        Some(self.methods.clone())
+    }
+}
+
+/// Matches every request, regardless of method. Used for delegated sub-routers (see
+/// `router::builder::DrawRoutes::delegate`), which are responsible for their own method
+/// negotiation once the request reaches them.
+pub struct AnyRouteMatcher;
+
+impl AnyRouteMatcher {
+    pub fn new() -> Self {
+        AnyRouteMatcher
+    }
+}
+
+impl RouteMatcher for AnyRouteMatcher {
+    fn is_match(&self, _state: &State, _req: &Request) -> Result<(), RouteNonMatch> {
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+//! Defines `Route`, the tree-node entry that pairs a `RouteMatcher` with a dispatch target, and
+//! `RouteImpl`, its concrete implementation used by `router::builder`.
+
+pub mod matcher;
+
+use std::marker::PhantomData;
+
+use futures::future;
+use hyper::{Request, Response, StatusCode};
+
+use handler::HandlerFuture;
+use state::State;
+use router::request::path::PathExtractor;
+use router::request::query_string::QueryStringExtractor;
+use router::route::dispatch::Dispatcher;
+use router::route::matcher::{RouteMatcher, RouteNonMatch};
+use router::tree::SegmentMapping;
+
+/// Whether a route is handled directly by this router (`Internal`), or forwarded wholesale to
+/// another, independently-built `Router` (`External`), as installed by
+/// `router::builder::DrawRoutes::delegate`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Delegation {
+    /// The route dispatches directly to a `Handler` within this `Router`.
+    Internal,
+    /// The route forwards the request to another `Router`, which owns everything beneath the
+    /// path prefix that was matched to reach it.
+    External,
+}
+
+/// Carries the `PathExtractor`/`QueryStringExtractor` types associated with a `RouteImpl`. The
+/// extraction work is done by those types directly; this only exists to give `RouteImpl`
+/// somewhere to name them.
+pub struct Extractors<PE, QSE>
+where
+    PE: PathExtractor,
+    QSE: QueryStringExtractor,
+{
+    phantom: PhantomData<(PE, QSE)>,
+}
+
+impl<PE, QSE> Extractors<PE, QSE>
+where
+    PE: PathExtractor,
+    QSE: QueryStringExtractor,
+{
+    pub fn new() -> Self {
+        Extractors { phantom: PhantomData }
+    }
+}
+
+/// A single entry registered at a tree node: something that can decide whether it matches the
+/// current request and, if so, dispatch to its handler.
+pub trait Route {
+    /// Determines whether this route matches the current request, beyond the path segment match
+    /// that got the router here.
+    fn is_match(&self, state: &State, req: &Request) -> Result<(), RouteNonMatch>;
+
+    /// Whether this route dispatches internally, or delegates to another `Router`.
+    fn delegation(&self) -> Delegation;
+
+    /// Dispatches the request to this route's handler (via its `Dispatcher`, which runs the
+    /// associated pipeline chain first), after giving this route's `PathExtractor`/
+    /// `QueryStringExtractor` a chance to populate `State` from `segment_mapping`/the request's
+    /// query string.
+    fn dispatch(&self, state: State, req: Request, segment_mapping: SegmentMapping) -> Box<HandlerFuture>;
+}
+
+/// The concrete `Route` implementation created by `router::builder` for every route, whether
+/// registered via `get`/`post`/`route_to`/... or `delegate`.
+pub struct RouteImpl<M, PE, QSE>
+where
+    M: RouteMatcher,
+    PE: PathExtractor,
+    QSE: QueryStringExtractor,
+{
+    matcher: M,
+    dispatcher: Box<Dispatcher + Send + Sync>,
+    extractors: Extractors<PE, QSE>,
+    delegation: Delegation,
+}
+
+impl<M, PE, QSE> RouteImpl<M, PE, QSE>
+where
+    M: RouteMatcher,
+    PE: PathExtractor,
+    QSE: QueryStringExtractor,
+{
+    pub fn new(
+        matcher: M,
+        dispatcher: Box<Dispatcher + Send + Sync>,
+        extractors: Extractors<PE, QSE>,
+        delegation: Delegation,
+    ) -> Self {
+        RouteImpl { matcher, dispatcher, extractors, delegation }
+    }
+}
+
+impl<M, PE, QSE> Route for RouteImpl<M, PE, QSE>
+where
+    M: RouteMatcher,
+    PE: PathExtractor,
+    QSE: QueryStringExtractor,
+{
+    fn is_match(&self, state: &State, req: &Request) -> Result<(), RouteNonMatch> {
+        self.matcher.is_match(state, req)
+    }
+
+    fn delegation(&self) -> Delegation {
+        self.delegation
+    }
+
+    fn dispatch(&self, mut state: State, req: Request, segment_mapping: SegmentMapping) -> Box<HandlerFuture> {
+        let _ = &self.extractors;
+
+        if let Err(_) = PE::extract(&mut state, segment_mapping) {
+            return extraction_failure_response(state);
+        }
+
+        let query = req.query().map(str::to_owned);
+        if let Err(_) = QSE::extract(&mut state, query.as_ref().map(String::as_str)) {
+            return extraction_failure_response(state);
+        }
+
+        self.dispatcher.dispatch(state, req)
+    }
+}
+
+fn extraction_failure_response(state: State) -> Box<HandlerFuture> {
+    Box::new(future::ok((
+        state,
+        Response::new().with_status(StatusCode::BadRequest),
+    )))
+}
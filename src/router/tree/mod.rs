@@ -0,0 +1,6 @@
+//! Defines the router tree: the structure that `router::builder` constructs and that `Router`
+//! walks at request time to find the `Route`(s) registered for a path.
+
+pub mod node;
+
+pub use self::node::{Node, NodeBuilder, PathSegment, SegmentMapping, SegmentType, TreeBuilder};
@@ -0,0 +1,210 @@
+//! Defines the nodes that make up the router tree, and the path-segment matching algorithm used
+//! to find the node (and captured path segments) for an incoming request path.
+
+use router::route::Route;
+
+/// The category of a single path segment within the router tree, as produced by
+/// `router::builder::build_subtree` from a route path such as `"/hello/:name/*rest"`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SegmentType {
+    /// A literal path segment, e.g. `"hello"` in `"/hello/world"`.
+    Static,
+    /// A named, single-segment capture, e.g. `":name"` in `"/hello/:name"`.
+    Dynamic,
+    /// A named, greedy capture of one or more trailing segments, e.g. `"*path"` in
+    /// `"/assets/*path"`. Must be the last segment of a route path.
+    Glob,
+}
+
+/// A single captured path segment, exposed to a `PathExtractor` via `SegmentMapping`.
+///
+/// For `Dynamic` segments this is the single matched segment; for `Glob` segments, the matched
+/// remainder is joined back together with `/` into one value, so a handler can treat it as a
+/// single relative path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathSegment {
+    value: String,
+}
+
+impl PathSegment {
+    fn new<S: Into<String>>(value: S) -> Self {
+        PathSegment { value: value.into() }
+    }
+
+    /// The value captured for this segment, as it appears in the request path (it is not
+    /// percent-decoded).
+    pub fn val(&self) -> &str {
+        &self.value
+    }
+}
+
+/// The path segments captured while matching a request path against the tree, keyed by the name
+/// given to each `Dynamic`/`Glob` segment (e.g. `"name"` for `":name"`, `"path"` for `"*path"`).
+#[derive(Clone, Debug, Default)]
+pub struct SegmentMapping {
+    segments: Vec<(String, Vec<PathSegment>)>,
+}
+
+impl SegmentMapping {
+    fn new() -> Self {
+        SegmentMapping { segments: Vec::new() }
+    }
+
+    fn insert<S: Into<String>>(&mut self, name: S, value: PathSegment) {
+        self.segments.push((name.into(), vec![value]));
+    }
+
+    /// The captured values for the segment named `name`, if any were captured under that name.
+    pub fn get(&self, name: &str) -> Option<&Vec<PathSegment>> {
+        self.segments.iter().find(|&&(ref n, _)| n == name).map(|&(_, ref v)| v)
+    }
+}
+
+/// Builds up a `Node` tree one path segment at a time, as routes are registered via
+/// `router::builder::DrawRoutes`. Finalized into an immutable `Node` via `finalize`.
+pub struct NodeBuilder {
+    segment: String,
+    segment_type: SegmentType,
+    routes: Vec<Box<Route + Send + Sync>>,
+    children: Vec<NodeBuilder>,
+}
+
+impl NodeBuilder {
+    pub fn new<S: Into<String>>(segment: S, segment_type: SegmentType) -> Self {
+        NodeBuilder {
+            segment: segment.into(),
+            segment_type,
+            routes: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn has_child(&self, segment: &str, segment_type: SegmentType) -> bool {
+        self.children
+            .iter()
+            .any(|c| c.segment == segment && c.segment_type == segment_type)
+    }
+
+    pub fn add_child(&mut self, child: NodeBuilder) {
+        self.children.push(child);
+    }
+
+    pub fn borrow_mut_child(
+        &mut self,
+        segment: &str,
+        segment_type: SegmentType,
+    ) -> Option<&mut NodeBuilder> {
+        self.children
+            .iter_mut()
+            .find(|c| c.segment == segment && c.segment_type == segment_type)
+    }
+
+    pub fn add_route(&mut self, route: Box<Route + Send + Sync>) {
+        self.routes.push(route);
+    }
+
+    /// Finalizes this subtree into an immutable `Node`, ordering children so that matching
+    /// prefers `Static` children, then `Dynamic`, then `Glob`, regardless of registration order.
+    pub fn finalize(self) -> Node {
+        let mut children: Vec<Node> = self.children.into_iter().map(NodeBuilder::finalize).collect();
+        children.sort_by_key(|n| match n.segment_type {
+            SegmentType::Static => 0,
+            SegmentType::Dynamic => 1,
+            SegmentType::Glob => 2,
+        });
+
+        Node {
+            segment: self.segment,
+            segment_type: self.segment_type,
+            routes: self.routes,
+            children,
+        }
+    }
+}
+
+/// An immutable node in the router tree, as built by `TreeBuilder`/`NodeBuilder::finalize`.
+pub struct Node {
+    segment: String,
+    segment_type: SegmentType,
+    routes: Vec<Box<Route + Send + Sync>>,
+    children: Vec<Node>,
+}
+
+impl Node {
+    /// The routes registered directly at this node.
+    pub fn routes(&self) -> &[Box<Route + Send + Sync>] {
+        &self.routes
+    }
+
+    /// Matches `segments` (the request path, already split on `/`) against this subtree,
+    /// returning the node it resolves to along with the path segments captured by any
+    /// `Dynamic`/`Glob` segments traversed, or `None` if no node matches.
+    ///
+    /// Matching prefers a `Static` child over a `Dynamic` child over a `Glob` child at each
+    /// level (enforced by the order established in `NodeBuilder::finalize`), so a more specific
+    /// route always wins over a catch-all one.
+    pub fn match_path<'n, 's>(&'n self, segments: &[&'s str]) -> Option<(&'n Node, SegmentMapping)> {
+        let mut mapping = SegmentMapping::new();
+        self.match_segments(segments, &mut mapping).map(|node| (node, mapping))
+    }
+
+    fn match_segments<'n, 's>(
+        &'n self,
+        segments: &[&'s str],
+        mapping: &mut SegmentMapping,
+    ) -> Option<&'n Node> {
+        match segments.split_first() {
+            None => Some(self),
+            Some((head, tail)) => {
+                for child in &self.children {
+                    match child.segment_type {
+                        SegmentType::Static => {
+                            if child.segment == *head {
+                                if let Some(node) = child.match_segments(tail, mapping) {
+                                    return Some(node);
+                                }
+                            }
+                        }
+                        SegmentType::Dynamic => {
+                            let mut speculative = mapping.clone();
+                            speculative.insert(child.segment.clone(), PathSegment::new(*head));
+                            if let Some(node) = child.match_segments(tail, &mut speculative) {
+                                *mapping = speculative;
+                                return Some(node);
+                            }
+                        }
+                        SegmentType::Glob => {
+                            // `segments` is always non-empty here (we're in the `Some((head,
+                            // tail))` arm), so a `Glob` child always has at least one segment
+                            // to capture and matches unconditionally.
+                            let joined = segments.join("/");
+                            mapping.insert(child.segment.clone(), PathSegment::new(joined));
+                            return Some(child);
+                        }
+                    }
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Builds the root of a router tree, handed to `RouterBuilder`/`ScopeBuilder` while routes are
+/// being registered.
+pub struct TreeBuilder {
+    root: NodeBuilder,
+}
+
+impl TreeBuilder {
+    pub fn new() -> Self {
+        TreeBuilder { root: NodeBuilder::new("", SegmentType::Static) }
+    }
+
+    pub fn borrow_root_mut(&mut self) -> &mut NodeBuilder {
+        &mut self.root
+    }
+
+    pub fn finalize(self) -> Node {
+        self.root.finalize()
+    }
+}